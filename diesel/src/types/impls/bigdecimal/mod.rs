@@ -0,0 +1,205 @@
+extern crate bigdecimal;
+extern crate num;
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::io::prelude::*;
+
+use self::bigdecimal::BigDecimal;
+use self::num::bigint::{BigInt, Sign};
+use self::num::ToPrimitive;
+
+use backend::Pg;
+use types::{self, FromSql, ToSql, IsNull};
+use super::floats::PgNumeric;
+
+#[derive(Debug, Clone, Copy)]
+struct NumericToBigDecimalError;
+
+impl ::std::fmt::Display for NumericToBigDecimalError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "NaN is not (yet) supported by BigDecimal")
+    }
+}
+
+impl Error for NumericToBigDecimalError {
+    fn description(&self) -> &str {
+        "NaN is not (yet) supported by BigDecimal"
+    }
+}
+
+impl TryFrom<PgNumeric> for BigDecimal {
+    type Error = Box<Error>;
+
+    fn try_from(numeric: PgNumeric) -> Result<Self, Self::Error> {
+        let (sign, weight, scale, digits) = match numeric {
+            PgNumeric::Positive { weight, scale, digits } => (Sign::Plus, weight, scale, digits),
+            PgNumeric::Negative { weight, scale, digits } => (Sign::Minus, weight, scale, digits),
+            PgNumeric::NaN => return Err(Box::new(NumericToBigDecimalError)),
+        };
+
+        if digits.is_empty() {
+            return Ok(BigDecimal::new(BigInt::default(), scale as i64));
+        }
+
+        let mut value = BigInt::default();
+        for digit in &digits {
+            value = value * BigInt::from(10_000) + BigInt::from(*digit);
+        }
+
+        // `value` is the digit groups read as one big base-10000 integer,
+        // so its least significant group sits `digits.len() - 1 - weight`
+        // NBASE places to the right of the decimal point.
+        let unscaled_exponent = digits.len() as i64 - weight as i64 - 1;
+        let raw_scale = unscaled_exponent * 4;
+        let value = if sign == Sign::Minus { -value } else { value };
+
+        Ok(BigDecimal::new(value, raw_scale).with_scale(scale as i64))
+    }
+}
+
+impl<'a> From<&'a BigDecimal> for PgNumeric {
+    fn from(decimal: &'a BigDecimal) -> Self {
+        let (int_val, exponent) = decimal.as_bigint_and_exponent();
+        let sign = int_val.sign();
+        let int_val = int_val.abs();
+
+        // `exponent` from bigdecimal is the number of digits after the
+        // decimal point; a negative exponent means the value is an
+        // integer whose trailing zeroes are carried by the exponent, so
+        // they are folded into `int_val` here.
+        let (int_val, scale) = if exponent < 0 {
+            (int_val * pow_10_base10((-exponent) as u32), 0u16)
+        } else {
+            (int_val, exponent as u16)
+        };
+
+        if int_val == BigInt::default() {
+            return PgNumeric::Positive { weight: 0, scale: scale, digits: vec![] };
+        }
+
+        // Pad the fractional digits out to a multiple of 4 so base-10000
+        // groups line up on the decimal point the same way Postgres does.
+        let padded_frac_groups = (scale as u32 + 3) / 4;
+        let pad = padded_frac_groups * 4 - scale as u32;
+        let int_val = int_val * pow_10_base10(pad);
+
+        let mut digits = Vec::new();
+        let mut remaining = int_val;
+        let base = BigInt::from(10_000);
+        while remaining > BigInt::default() {
+            let digit = (&remaining % &base).to_i16()
+                .expect("a value reduced mod 10,000 always fits in an i16");
+            digits.push(digit);
+            remaining = remaining / &base;
+        }
+        digits.reverse();
+
+        let integer_groups = digits.len() as i64 - padded_frac_groups as i64;
+        let weight = (integer_groups - 1) as i16;
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        let mut leading_zeros: i16 = 0;
+        while digits.first() == Some(&0) {
+            digits.remove(0);
+            leading_zeros += 1;
+        }
+        let weight = weight - leading_zeros;
+
+        match sign {
+            Sign::Minus => PgNumeric::Negative { weight: weight, scale: scale, digits: digits },
+            _ => PgNumeric::Positive { weight: weight, scale: scale, digits: digits },
+        }
+    }
+}
+
+fn pow_10_base10(exponent: u32) -> BigInt {
+    let mut result = BigInt::from(1);
+    let base = BigInt::from(10);
+    for _ in 0..exponent {
+        result = result * base.clone();
+    }
+    result
+}
+
+impl FromSql<types::Numeric, Pg> for BigDecimal {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let numeric = try!(PgNumeric::from_sql(bytes));
+        BigDecimal::try_from(numeric)
+    }
+}
+
+impl ToSql<types::Numeric, Pg> for BigDecimal {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let numeric = PgNumeric::from(self);
+        numeric.to_sql(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::error::Error;
+    use std::str::FromStr;
+
+    use super::bigdecimal::BigDecimal;
+    use super::PgNumeric;
+
+    use backend::Pg;
+    use types::{self, FromSql, ToSql};
+
+    fn round_trip(value: &str) {
+        let decimal = BigDecimal::from_str(value).unwrap();
+        let numeric = PgNumeric::from(&decimal);
+        let back = BigDecimal::try_from(numeric).unwrap();
+        assert_eq!(decimal, back);
+    }
+
+    #[test]
+    fn round_trips_positive_and_negative_values() {
+        round_trip("1.5");
+        round_trip("-1.5");
+        round_trip("123456789.987654321");
+        round_trip("-123456789.987654321");
+    }
+
+    #[test]
+    fn round_trips_zero() {
+        round_trip("0");
+        round_trip("0.000");
+    }
+
+    #[test]
+    fn zero_preserves_the_declared_scale() {
+        let numeric = PgNumeric::Positive { weight: 0, scale: 3, digits: vec![] };
+        let decimal = BigDecimal::try_from(numeric).unwrap();
+        let (_, exponent) = decimal.as_bigint_and_exponent();
+        assert_eq!(3, exponent);
+    }
+
+    #[test]
+    fn wire_round_trip_via_to_sql_and_from_sql() {
+        let decimal = BigDecimal::from_str("-123456789.987654321").unwrap();
+        let mut bytes = vec![];
+        ToSql::<types::Numeric, Pg>::to_sql(&decimal, &mut bytes).unwrap();
+        let back = <BigDecimal as FromSql<types::Numeric, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(decimal, back);
+    }
+
+    #[test]
+    fn round_trips_values_with_no_fractional_part() {
+        round_trip("4");
+        round_trip("-4");
+    }
+
+    #[test]
+    fn nan_is_not_representable() {
+        let error = BigDecimal::try_from(PgNumeric::NaN).unwrap_err();
+        assert_eq!(
+            "NaN is not (yet) supported by BigDecimal",
+            error.description(),
+        );
+    }
+}
@@ -0,0 +1,157 @@
+extern crate byteorder;
+extern crate chrono;
+
+use std::error::Error;
+use std::io::prelude::*;
+
+use self::byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use self::chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+use backend::Backend;
+use types::{self, FromSql, ToSql, IsNull};
+
+#[derive(Debug, Clone)]
+struct DateTimeRangeError(String);
+
+impl ::std::fmt::Display for DateTimeRangeError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for DateTimeRangeError {
+    fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+// Postgres measures time relative to 2000-01-01, not the Unix epoch that
+// chrono's `Naive*` types use, so every conversion has to cross that gap.
+fn pg_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)
+}
+
+fn pg_epoch_date() -> NaiveDate {
+    NaiveDate::from_ymd(2000, 1, 1)
+}
+
+impl<DB: Backend<RawValue=[u8]>> FromSql<types::Timestamp, DB> for NaiveDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        let offset = try!(bytes.read_i64::<BigEndian>());
+        match pg_epoch().checked_add_signed(Duration::microseconds(offset)) {
+            Some(dt) => Ok(dt),
+            None => Err(Box::new(DateTimeRangeError(
+                format!("Overflow occurred while trying to interpret {} as a timestamp", offset)
+            )) as Box<Error>),
+        }
+    }
+}
+
+impl<DB: Backend> ToSql<types::Timestamp, DB> for NaiveDateTime {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let time = match self.signed_duration_since(pg_epoch()).num_microseconds() {
+            Some(time) => time,
+            None => return Err(Box::new(DateTimeRangeError(
+                format!("{:?} as microseconds is too large to fit in an i64", self)
+            )) as Box<Error>),
+        };
+        out.write_i64::<BigEndian>(time)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend<RawValue=[u8]>> FromSql<types::Date, DB> for NaiveDate {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        let offset = try!(bytes.read_i32::<BigEndian>());
+        match pg_epoch_date().checked_add_signed(Duration::days(offset as i64)) {
+            Some(date) => Ok(date),
+            None => Err(Box::new(DateTimeRangeError(
+                format!("Overflow occurred while trying to interpret {} as a date", offset)
+            )) as Box<Error>),
+        }
+    }
+}
+
+impl<DB: Backend> ToSql<types::Date, DB> for NaiveDate {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let days_since_epoch = self.signed_duration_since(pg_epoch_date()).num_days();
+        out.write_i32::<BigEndian>(days_since_epoch as i32)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend<RawValue=[u8]>> FromSql<types::Time, DB> for NaiveTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let mut bytes = not_none!(bytes);
+        let microseconds = try!(bytes.read_i64::<BigEndian>());
+        Ok(NaiveTime::from_hms(0, 0, 0) + Duration::microseconds(microseconds))
+    }
+}
+
+impl<DB: Backend> ToSql<types::Time, DB> for NaiveTime {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let duration = self.signed_duration_since(NaiveTime::from_hms(0, 0, 0));
+        let micros = match duration.num_microseconds() {
+            Some(micros) => micros,
+            None => return Err(Box::new(DateTimeRangeError(
+                format!("{:?} as microseconds is too large to fit in an i64", self)
+            )) as Box<Error>),
+        };
+        out.write_i64::<BigEndian>(micros)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    use backend::Pg;
+    use types::{self, FromSql, ToSql};
+
+    fn round_trip_timestamp(value: NaiveDateTime) {
+        let mut bytes = vec![];
+        ToSql::<types::Timestamp, Pg>::to_sql(&value, &mut bytes).unwrap();
+        let back = <NaiveDateTime as FromSql<types::Timestamp, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(value, back);
+    }
+
+    fn round_trip_date(value: NaiveDate) {
+        let mut bytes = vec![];
+        ToSql::<types::Date, Pg>::to_sql(&value, &mut bytes).unwrap();
+        let back = <NaiveDate as FromSql<types::Date, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(value, back);
+    }
+
+    fn round_trip_time(value: NaiveTime) {
+        let mut bytes = vec![];
+        ToSql::<types::Time, Pg>::to_sql(&value, &mut bytes).unwrap();
+        let back = <NaiveTime as FromSql<types::Time, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(value, back);
+    }
+
+    #[test]
+    fn round_trips_timestamps_before_and_after_the_pg_epoch() {
+        round_trip_timestamp(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0));
+        round_trip_timestamp(NaiveDate::from_ymd(1990, 6, 15).and_hms(12, 30, 45));
+        round_trip_timestamp(NaiveDate::from_ymd(2020, 12, 31).and_hms_micro(23, 59, 59, 123_456));
+    }
+
+    #[test]
+    fn round_trips_dates_before_and_after_the_pg_epoch() {
+        round_trip_date(NaiveDate::from_ymd(2000, 1, 1));
+        round_trip_date(NaiveDate::from_ymd(1970, 1, 1));
+        round_trip_date(NaiveDate::from_ymd(2100, 3, 4));
+    }
+
+    #[test]
+    fn round_trips_times_of_day() {
+        round_trip_time(NaiveTime::from_hms(0, 0, 0));
+        round_trip_time(NaiveTime::from_hms_micro(23, 59, 59, 999_999));
+    }
+}
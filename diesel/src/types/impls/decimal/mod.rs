@@ -0,0 +1,225 @@
+extern crate rust_decimal;
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::io::prelude::*;
+
+use self::rust_decimal::Decimal;
+
+use backend::Pg;
+use types::{self, FromSql, ToSql, IsNull};
+use super::floats::PgNumeric;
+
+// The mantissa of a `Decimal` is 96 bits wide, so it cannot hold more than
+// roughly 28-29 significant decimal digits.
+const MAX_MANTISSA: u128 = (1u128 << 96) - 1;
+
+// `rust_decimal::Decimal` caps its scale at 28, but Postgres' `dscale` can be
+// as large as 16383 (e.g. a tiny value like `1e-30`), so this has to be
+// checked explicitly rather than relying on the mantissa bound above.
+const MAX_SCALE: u16 = 28;
+
+// 10^39 overflows `u128` (whose max is ~3.4e38), so any exponent beyond this
+// can never produce a mantissa that fits `Decimal` either; reject it before
+// calling `pow` rather than panicking/wrapping on the overflow itself.
+const MAX_POW10_EXPONENT: i64 = 38;
+
+#[derive(Debug, Clone, Copy)]
+struct NumericToDecimalError(&'static str);
+
+impl ::std::fmt::Display for NumericToDecimalError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for NumericToDecimalError {
+    fn description(&self) -> &str {
+        self.0
+    }
+}
+
+fn checked_pow10(exponent: i64) -> Result<u128, Box<Error>> {
+    if exponent < 0 || exponent > MAX_POW10_EXPONENT {
+        return Err(Box::new(NumericToDecimalError("numeric value out of range for Decimal")));
+    }
+    Ok(10u128.pow(exponent as u32))
+}
+
+impl TryFrom<PgNumeric> for Decimal {
+    type Error = Box<Error>;
+
+    fn try_from(numeric: PgNumeric) -> Result<Self, Self::Error> {
+        let (negative, weight, scale, digits) = match numeric {
+            PgNumeric::Positive { weight, scale, digits } => (false, weight, scale, digits),
+            PgNumeric::Negative { weight, scale, digits } => (true, weight, scale, digits),
+            PgNumeric::NaN => {
+                return Err(Box::new(NumericToDecimalError("NaN is not representable as a Decimal")));
+            }
+        };
+
+        if scale > MAX_SCALE {
+            return Err(Box::new(NumericToDecimalError("numeric value out of range for Decimal")));
+        }
+
+        if digits.is_empty() {
+            return Ok(Decimal::new(0, scale as u32));
+        }
+
+        let mut mantissa: u128 = 0;
+        for digit in &digits {
+            mantissa = mantissa.checked_mul(10_000)
+                .and_then(|m| m.checked_add(*digit as u128))
+                .ok_or_else(|| Box::new(NumericToDecimalError("numeric value out of range for Decimal")) as Box<Error>)?;
+        }
+
+        // `mantissa` currently holds `(digits.len() - weight - 1) * 4`
+        // fractional decimal digits; reconcile that against the numeric's declared `scale` by
+        // either dropping the extra alignment digits or padding with zeroes.
+        let available_scale = (digits.len() as i64 - weight as i64 - 1) * 4;
+        let mantissa = if available_scale > scale as i64 {
+            mantissa / try!(checked_pow10(available_scale - scale as i64))
+        } else {
+            mantissa.checked_mul(try!(checked_pow10(scale as i64 - available_scale)))
+                .ok_or_else(|| Box::new(NumericToDecimalError("numeric value out of range for Decimal")) as Box<Error>)?
+        };
+
+        if mantissa > MAX_MANTISSA {
+            return Err(Box::new(NumericToDecimalError("numeric value out of range for Decimal")));
+        }
+
+        Ok(Decimal::from_parts(
+            mantissa as u32,
+            (mantissa >> 32) as u32,
+            (mantissa >> 64) as u32,
+            negative,
+            scale as u32,
+        ))
+    }
+}
+
+impl<'a> From<&'a Decimal> for PgNumeric {
+    fn from(decimal: &'a Decimal) -> Self {
+        let scale = decimal.scale() as u16;
+        let mantissa = decimal.mantissa().unsigned_abs();
+        let negative = decimal.is_sign_negative();
+
+        if mantissa == 0 {
+            return PgNumeric::Positive { weight: 0, scale: scale, digits: vec![] };
+        }
+
+        // Pad the fractional digits out to a multiple of 4 so base-10000
+        // groups line up on the decimal point the same way Postgres does.
+        let padded_frac_groups = (scale as u32 + 3) / 4;
+        let pad = padded_frac_groups * 4 - scale as u32;
+        let mut remaining = mantissa * 10u128.pow(pad);
+
+        let mut digits = Vec::new();
+        while remaining > 0 {
+            digits.push((remaining % 10_000) as i16);
+            remaining /= 10_000;
+        }
+        digits.reverse();
+
+        let integer_groups = digits.len() as i64 - padded_frac_groups as i64;
+        let mut weight = (integer_groups - 1) as i16;
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        while digits.first() == Some(&0) {
+            digits.remove(0);
+            weight -= 1;
+        }
+
+        if negative {
+            PgNumeric::Negative { weight: weight, scale: scale, digits: digits }
+        } else {
+            PgNumeric::Positive { weight: weight, scale: scale, digits: digits }
+        }
+    }
+}
+
+impl FromSql<types::Numeric, Pg> for Decimal {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let numeric = try!(PgNumeric::from_sql(bytes));
+        Decimal::try_from(numeric)
+    }
+}
+
+impl ToSql<types::Numeric, Pg> for Decimal {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let numeric = PgNumeric::from(self);
+        numeric.to_sql(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    use super::rust_decimal::Decimal;
+    use super::PgNumeric;
+
+    use backend::Pg;
+    use types::{self, FromSql, ToSql};
+
+    fn round_trip(value: &str) {
+        let decimal = Decimal::from_str(value).unwrap();
+        let numeric = PgNumeric::from(&decimal);
+        let back = Decimal::try_from(numeric).unwrap();
+        assert_eq!(decimal, back);
+    }
+
+    #[test]
+    fn round_trips_positive_and_negative_values() {
+        round_trip("1.5");
+        round_trip("-1.5");
+        round_trip("123456789.987654321");
+        round_trip("-123456789.987654321");
+    }
+
+    #[test]
+    fn round_trips_zero() {
+        round_trip("0");
+        round_trip("0.000");
+    }
+
+    #[test]
+    fn zero_preserves_the_declared_scale() {
+        let numeric = PgNumeric::Positive { weight: 0, scale: 3, digits: vec![] };
+        let decimal = Decimal::try_from(numeric).unwrap();
+        assert_eq!(3, decimal.scale());
+    }
+
+    #[test]
+    fn nan_is_not_representable() {
+        assert!(Decimal::try_from(PgNumeric::NaN).is_err());
+    }
+
+    #[test]
+    fn exponent_out_of_range_is_an_error_not_a_panic() {
+        let numeric = PgNumeric::Positive {
+            weight: 1000,
+            scale: 0,
+            digits: vec![1],
+        };
+        assert!(Decimal::try_from(numeric).is_err());
+    }
+
+    #[test]
+    fn scale_beyond_decimals_range_is_an_error_not_garbage() {
+        let numeric = PgNumeric::Positive { weight: 0, scale: 30, digits: vec![1] };
+        assert!(Decimal::try_from(numeric).is_err());
+    }
+
+    #[test]
+    fn wire_round_trip_via_to_sql_and_from_sql() {
+        let decimal = Decimal::from_str("-123456789.987654321").unwrap();
+        let mut bytes = vec![];
+        ToSql::<types::Numeric, Pg>::to_sql(&decimal, &mut bytes).unwrap();
+        let back = <Decimal as FromSql<types::Numeric, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(decimal, back);
+    }
+}
@@ -0,0 +1,271 @@
+extern crate fixed;
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::io::prelude::*;
+
+use self::fixed::types::I80F48;
+
+use backend::Pg;
+use types::{self, FromSql, ToSql, IsNull};
+use super::floats::PgNumeric;
+
+// I80F48 carries 48 fractional bits, which round-trips exactly through 16
+// decimal digits of scale (10^16 distinguishes more values than 2^48 has);
+// every nonzero value this module emits uses that fixed dscale rather than
+// Postgres' usual per-value scale (zero is emitted with a scale of 0).
+const FIXED_SCALE: u16 = 16;
+const NBASE_GROUP: u32 = 4;
+
+// 10^39 overflows `u128` (whose max is ~3.4e38); any exponent beyond this
+// can never produce a mantissa that fits `I80F48` either, so reject it
+// before calling `pow` rather than panicking/wrapping on the overflow.
+const MAX_POW10_EXPONENT: i64 = 38;
+
+#[derive(Debug, Clone, Copy)]
+struct NumericToFixedError(&'static str);
+
+impl ::std::fmt::Display for NumericToFixedError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for NumericToFixedError {
+    fn description(&self) -> &str {
+        self.0
+    }
+}
+
+fn overflow_err() -> Box<Error> {
+    Box::new(NumericToFixedError("numeric value out of range for I80F48"))
+}
+
+fn checked_pow10(exponent: i64) -> Result<u128, Box<Error>> {
+    if exponent < 0 || exponent > MAX_POW10_EXPONENT {
+        return Err(overflow_err());
+    }
+    Ok(10u128.pow(exponent as u32))
+}
+
+// Rounds `numerator / denominator` to the nearest integer instead of
+// truncating, so converting a fixed-point fraction to decimal and back
+// recovers the exact original bits instead of drifting by up to 1 ULP.
+fn round_div(numerator: u128, denominator: u128) -> u128 {
+    (numerator + denominator / 2) / denominator
+}
+
+impl TryFrom<PgNumeric> for I80F48 {
+    type Error = Box<Error>;
+
+    fn try_from(numeric: PgNumeric) -> Result<Self, Self::Error> {
+        let (negative, weight, digits) = match numeric {
+            PgNumeric::Positive { weight, digits, .. } => (false, weight, digits),
+            PgNumeric::Negative { weight, digits, .. } => (true, weight, digits),
+            PgNumeric::NaN => {
+                return Err(Box::new(NumericToFixedError("NaN is not representable as an I80F48")));
+            }
+        };
+
+        if digits.is_empty() {
+            return Ok(I80F48::from_num(0));
+        }
+
+        let mut mantissa: u128 = 0;
+        for digit in &digits {
+            mantissa = mantissa.checked_mul(10_000)
+                .and_then(|m| m.checked_add(*digit as u128))
+                .ok_or_else(overflow_err)?;
+        }
+
+        // `mantissa` holds `(digits.len() - weight - 1) * 4` fractional
+        // decimal digits; reconcile that against the 16 we need before turning it into
+        // raw fixed-point bits.
+        let available_scale = (digits.len() as i64 - weight as i64 - 1) * NBASE_GROUP as i64;
+        let mantissa = if available_scale > FIXED_SCALE as i64 {
+            mantissa / try!(checked_pow10(available_scale - FIXED_SCALE as i64))
+        } else {
+            mantissa.checked_mul(try!(checked_pow10(FIXED_SCALE as i64 - available_scale)))
+                .ok_or_else(overflow_err)?
+        };
+
+        // Split into whole and fractional parts before multiplying by
+        // `2^48` so the intermediate product stays within `u128` for any
+        // mantissa that itself fits `I80F48`, rather than overflowing the
+        // way a single `mantissa * 2^48` would for large values. Rounding
+        // the fractional contribution (rather than truncating it) is what
+        // makes this the exact inverse of `TryFrom<&I80F48>` below.
+        let scale_divisor = try!(checked_pow10(FIXED_SCALE as i64));
+        let whole = mantissa / scale_divisor;
+        let frac = mantissa % scale_divisor;
+        let bits = whole.checked_mul(1u128 << 48)
+            .and_then(|b| b.checked_add(round_div(frac * (1u128 << 48), scale_divisor)))
+            .ok_or_else(overflow_err)?;
+
+        if bits > i128::max_value() as u128 {
+            return Err(overflow_err());
+        }
+        let value = I80F48::from_bits(bits as i128);
+        Ok(if negative { -value } else { value })
+    }
+}
+
+impl<'a> TryFrom<&'a I80F48> for PgNumeric {
+    type Error = Box<Error>;
+
+    fn try_from(value: &'a I80F48) -> Result<Self, Self::Error> {
+        // Working from the raw bits (rather than `value.abs()`) sidesteps
+        // the two's-complement overflow on `I80F48::MIN`, which has no
+        // positive counterpart in the same type.
+        let bits = value.to_bits();
+        if bits == 0 {
+            return Ok(PgNumeric::Positive { weight: 0, scale: 0, digits: vec![0] });
+        }
+        let negative = bits < 0;
+        let magnitude = bits.unsigned_abs();
+
+        let integer_bits = magnitude >> 48;
+        let frac_bits = magnitude & ((1u128 << 48) - 1);
+        let scale_multiplier = try!(checked_pow10(FIXED_SCALE as i64));
+
+        // As with decoding, compute the whole and fractional contributions
+        // separately so the fractional `* 10^16` rescale can't overflow
+        // `u128`, and round (rather than truncate) the fractional
+        // contribution so this is the exact inverse of `TryFrom<PgNumeric>`
+        // above. An integer part above `u128::MAX / 10^16` (about 3.4e22)
+        // still overflows once scaled and is returned as an error.
+        let scaled = integer_bits.checked_mul(scale_multiplier)
+            .and_then(|v| v.checked_add(round_div(frac_bits * scale_multiplier, 1u128 << 48)))
+            .ok_or_else(overflow_err)?;
+
+        if scaled == 0 {
+            return Ok(PgNumeric::Positive { weight: 0, scale: 0, digits: vec![0] });
+        }
+
+        // Split `scaled` (which is `value * 10^16`, i.e. `value` expressed
+        // in units of `10000^-4`) into base-10000 groups the same way
+        // `bigdecimal`/`decimal` do, then shift `weight` down by the 4
+        // groups of padding so it lines up on the real decimal point.
+        let mut digits = Vec::new();
+        let mut remaining = scaled;
+        while remaining > 0 {
+            digits.push((remaining % 10_000) as i16);
+            remaining /= 10_000;
+        }
+        digits.reverse();
+
+        let padded_frac_groups = FIXED_SCALE as i64 / NBASE_GROUP as i64;
+        let integer_groups = digits.len() as i64 - padded_frac_groups;
+        let mut weight = (integer_groups - 1) as i16;
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        while digits.first() == Some(&0) {
+            digits.remove(0);
+            weight -= 1;
+        }
+
+        Ok(if negative {
+            PgNumeric::Negative { weight: weight, scale: FIXED_SCALE, digits: digits }
+        } else {
+            PgNumeric::Positive { weight: weight, scale: FIXED_SCALE, digits: digits }
+        })
+    }
+}
+
+impl FromSql<types::Numeric, Pg> for I80F48 {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let numeric = try!(PgNumeric::from_sql(bytes));
+        I80F48::try_from(numeric)
+    }
+}
+
+impl ToSql<types::Numeric, Pg> for I80F48 {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let numeric = try!(PgNumeric::try_from(self));
+        numeric.to_sql(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::fixed::types::I80F48;
+    use super::PgNumeric;
+
+    use backend::Pg;
+    use types::{self, FromSql, ToSql};
+
+    fn round_trip(value: I80F48) {
+        let numeric = PgNumeric::try_from(&value).unwrap();
+        let back = I80F48::try_from(numeric).unwrap();
+        assert_eq!(value, back);
+    }
+
+    #[test]
+    fn round_trips_zero() {
+        round_trip(I80F48::from_num(0));
+    }
+
+    #[test]
+    fn round_trips_integers() {
+        round_trip(I80F48::from_num(4));
+        round_trip(I80F48::from_num(-4));
+    }
+
+    #[test]
+    fn round_trips_fractional_values() {
+        round_trip(I80F48::from_num(1.5));
+        round_trip(I80F48::from_num(-1.5));
+    }
+
+    #[test]
+    fn round_trips_the_smallest_representable_fraction() {
+        // Regression test: a naive truncating rescale collapses this value
+        // (2^-48, the smallest nonzero magnitude I80F48 can represent) to 0.
+        round_trip(I80F48::from_bits(1));
+        round_trip(I80F48::from_bits(-1));
+    }
+
+    #[test]
+    fn round_trips_large_magnitudes() {
+        // An integer part (2^70) large enough that a single
+        // `mantissa * 2^48` would overflow `u128`, yet small enough to fit
+        // once scaled by 10^16.
+        round_trip(I80F48::from_bits((1i128 << 70) << 48));
+        round_trip(I80F48::from_bits(-((1i128 << 70) << 48)));
+    }
+
+    #[test]
+    fn nan_is_not_representable() {
+        assert!(I80F48::try_from(PgNumeric::NaN).is_err());
+    }
+
+    #[test]
+    fn values_too_large_to_rescale_are_an_error_not_a_panic() {
+        // `I80F48::MIN`'s integer part overflows `u128` once scaled by
+        // 10^16; this must surface as an error, not panic or wrap.
+        assert!(PgNumeric::try_from(&I80F48::from_bits(i128::min_value())).is_err());
+    }
+
+    #[test]
+    fn exponent_out_of_range_is_an_error_not_a_panic() {
+        let numeric = PgNumeric::Positive {
+            weight: 1000,
+            scale: 0,
+            digits: vec![1],
+        };
+        assert!(I80F48::try_from(numeric).is_err());
+    }
+
+    #[test]
+    fn wire_round_trip_via_to_sql_and_from_sql() {
+        let value = I80F48::from_num(-1.5);
+        let mut bytes = vec![];
+        ToSql::<types::Numeric, Pg>::to_sql(&value, &mut bytes).unwrap();
+        let back = <I80F48 as FromSql<types::Numeric, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(value, back);
+    }
+}
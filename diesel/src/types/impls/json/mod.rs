@@ -0,0 +1,108 @@
+extern crate serde_json;
+
+use std::error::Error;
+use std::io::prelude::*;
+
+use self::serde_json::Value;
+
+use backend::Backend;
+use backend::Pg;
+use types::{self, FromSql, ToSql, IsNull};
+
+// Binary `jsonb` values are prefixed with a single version byte; `1` is the
+// only version Postgres has ever emitted.
+const JSONB_VERSION: u8 = 1;
+
+#[derive(Debug, Clone)]
+struct UnsupportedJsonbVersion(String);
+
+impl ::std::fmt::Display for UnsupportedJsonbVersion {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for UnsupportedJsonbVersion {
+    fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<DB: Backend<RawValue=[u8]>> FromSql<types::Json, DB> for Value {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        serde_json::from_slice(bytes).map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<DB: Backend> ToSql<types::Json, DB> for Value {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        serde_json::to_writer(out, self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl FromSql<types::Jsonb, Pg> for Value {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        match bytes.first() {
+            Some(&JSONB_VERSION) => {
+                serde_json::from_slice(&bytes[1..]).map_err(|e| Box::new(e) as Box<Error>)
+            }
+            Some(version) => Err(Box::new(UnsupportedJsonbVersion(
+                format!("Unsupported JSONB encoding version {}", version)
+            )) as Box<Error>),
+            None => Err(Box::new(UnsupportedJsonbVersion(
+                "Unexpected empty bytes for JSONB value".into()
+            )) as Box<Error>),
+        }
+    }
+}
+
+impl ToSql<types::Jsonb, Pg> for Value {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        try!(out.write_all(&[JSONB_VERSION]));
+        serde_json::to_writer(out, self)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serde_json::{self, Value};
+
+    use backend::Pg;
+    use types::{self, FromSql, ToSql};
+
+    #[test]
+    fn json_round_trips() {
+        let value: Value = serde_json::from_str(r#"{"a":1,"b":[true,null]}"#).unwrap();
+        let mut bytes = vec![];
+        ToSql::<types::Json, Pg>::to_sql(&value, &mut bytes).unwrap();
+        let back = <Value as FromSql<types::Json, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(value, back);
+    }
+
+    #[test]
+    fn jsonb_round_trips() {
+        let value: Value = serde_json::from_str(r#"{"a":1,"b":[true,null]}"#).unwrap();
+        let mut bytes = vec![];
+        ToSql::<types::Jsonb, Pg>::to_sql(&value, &mut bytes).unwrap();
+        let back = <Value as FromSql<types::Jsonb, Pg>>::from_sql(Some(&bytes)).unwrap();
+        assert_eq!(value, back);
+    }
+
+    #[test]
+    fn jsonb_rejects_an_unsupported_version_byte() {
+        let bytes = vec![2u8, b'{', b'}'];
+        assert!(<Value as FromSql<types::Jsonb, Pg>>::from_sql(Some(&bytes)).is_err());
+    }
+
+    #[test]
+    fn jsonb_rejects_empty_bytes() {
+        let bytes: Vec<u8> = vec![];
+        assert!(<Value as FromSql<types::Jsonb, Pg>>::from_sql(Some(&bytes)).is_err());
+    }
+}